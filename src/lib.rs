@@ -62,38 +62,26 @@
 //! ```
 //!
 //! ## Recipes
-//! You might want to override fetch or use a service worker to intercept HTTP calls in order to the
-//! call the Axum WASM app instead of the a HTTP server.
-//!
-//! **Converting a JavaScript Request to a WasmRequest**
-//! ```javascript
-//!  async function requestToWasmRequest(request) {
-//!     const method = request.method;
-//!     const url = request.url;
-//!     const headers = Object.fromEntries(request.headers.entries());
-//!
-//!     let body = null;
-//!     if (request.body !== null) {
-//!         body = await request.text();
-//!     }
-//!     return new WasmRequest(method, url, headers, body);
-//! }
-//! ```
+//! You might want to override fetch or use a service worker to intercept HTTP calls in order to
+//! call the Axum WASM app instead of an HTTP server.
 //!
-//! **Converting a WasmResponse to a JavaScript Response**
-//!
-//! ```javascript
-//! function wasmResponseToJsResponse(wasmResponse) {
-//!    const body = wasmResponse.body;
-//!    const status = parseInt(wasmResponse.status_code);
-//!    const jsHeaders = new Headers();
-//!    const headers = wasmResponse.headers;
-//!    for (let [key, value] of headers) {
-//!        jsHeaders.append(key, value);
-//!    }
-//!    return new Response(body, {status: status, headers: jsHeaders});
-//! }
+//! Both conversions now live in typed Rust, so there is no hand-written JavaScript glue to keep in
+//! sync. Use [`web_request_to_wasm_request`] to turn an intercepted [`web_sys::Request`] into a
+//! [`WasmRequest`], and [`wasm_response_to_web_response`] to build a [`web_sys::Response`] from the
+//! [`WasmResponse`] your app returns:
+//!
+//! ```no_run
+//! use axum_browser_adapter::{web_request_to_wasm_request, wasm_response_to_web_response};
+//!
+//! # async fn handler(request: web_sys::Request) -> web_sys::Response {
+//! let wasm_request = web_request_to_wasm_request(request).await;
+//! // ... run `wasm_app(wasm_request)` to obtain a `WasmResponse` ...
+//! # let wasm_response = todo!();
+//! wasm_response_to_web_response(wasm_response)
+//! # }
 //! ```
+//!
+//! For service workers you can skip the wiring entirely with [`ServiceWorkerExt::handle_fetch_events`].
 
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -103,10 +91,53 @@ use axum::response::Response;
 use axum::http::{Method, Request, Uri};
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::{from_value, to_value};
+use js_sys::Uint8Array;
 use wasm_bindgen::prelude::*;
 
+/// Reads a JS headers value into an order-preserving, duplicate-preserving list.
+///
+/// Accepts either an array of `[name, value]` pairs (which can carry repeated
+/// names such as `Set-Cookie`) or a plain object.
+fn js_value_to_headers(value: JsValue) -> Vec<(String, String)> {
+    if let Ok(pairs) = from_value::<Vec<(String, String)>>(value.clone()) {
+        return pairs;
+    }
+    from_value::<HashMap<String, String>>(value)
+        .map(|map| map.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Reads a JS body value that may be a `String`, an `ArrayBuffer`, or a
+/// `Uint8Array` into raw bytes. Returns `None` for `null`/`undefined`.
+fn js_body_to_bytes(value: &JsValue) -> Option<Vec<u8>> {
+    if value.is_null() || value.is_undefined() {
+        return None;
+    }
+    if let Some(text) = value.as_string() {
+        return Some(text.into_bytes());
+    }
+    if value.is_instance_of::<Uint8Array>() {
+        return Some(Uint8Array::new(value).to_vec());
+    }
+    if value.is_instance_of::<js_sys::ArrayBuffer>() {
+        return Some(Uint8Array::new(value).to_vec());
+    }
+    None
+}
+
 pub use axum_wasm_macros::wasm_compat;
 
+mod service_worker;
+pub use service_worker::{ServiceWorkerAdapter, ServiceWorkerExt};
+
+mod web_sys_conversion;
+pub use web_sys_conversion::{web_request_to_wasm_request, wasm_response_to_web_response};
+
+#[cfg(feature = "tracing")]
+mod tracing;
+#[cfg(feature = "tracing")]
+pub use tracing::init_tracing;
+
 #[wasm_bindgen]
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct WasmRequest {
@@ -115,19 +146,34 @@ pub struct WasmRequest {
     #[wasm_bindgen(skip)]
     pub url: String,
     #[wasm_bindgen(skip)]
-    pub headers: HashMap<String, String>,
+    pub headers: Vec<(String, String)>,
     #[wasm_bindgen(skip)]
-    pub body: Option<String>,
+    pub body: Option<Vec<u8>>,
 }
 
 #[wasm_bindgen]
 impl WasmRequest {
     #[wasm_bindgen(constructor)]
-    pub fn new(method: String, url: String, headers_js_value: JsValue, body: Option<String>) -> WasmRequest {
-        let headers: HashMap<String, String> = from_value(headers_js_value).unwrap();
+    pub fn new(method: String, url: String, headers_js_value: JsValue, body: JsValue) -> WasmRequest {
+        let headers = js_value_to_headers(headers_js_value);
+        let body = js_body_to_bytes(&body);
 
         WasmRequest { method, url, headers, body }
     }
+
+    /// The body decoded as UTF-8, or `None` when absent or not valid UTF-8.
+    #[wasm_bindgen(getter)]
+    pub fn body(&self) -> Option<String> {
+        self.body
+            .as_ref()
+            .and_then(|bytes| String::from_utf8(bytes.clone()).ok())
+    }
+
+    /// The raw body bytes as a `Uint8Array`.
+    #[wasm_bindgen(getter)]
+    pub fn body_bytes(&self) -> Option<Uint8Array> {
+        self.body.as_ref().map(|bytes| Uint8Array::from(bytes.as_slice()))
+    }
 }
 
 pub fn wasm_request_to_axum_request(wasm_request: &WasmRequest) -> Result<Request<Body>, Box<dyn std::error::Error>> {
@@ -146,7 +192,7 @@ pub fn wasm_request_to_axum_request(wasm_request: &WasmRequest) -> Result<Reques
     }
 
     let request = match &wasm_request.body {
-        Some(body_str) => request_builder.body(Body::from(body_str.to_owned()))?,
+        Some(body_bytes) => request_builder.body(Body::from(body_bytes.to_owned()))?,
         None => request_builder.body(Body::empty())?,
     };
 
@@ -159,9 +205,9 @@ pub struct WasmResponse {
     #[wasm_bindgen(skip)]
     pub status_code: String,
     #[wasm_bindgen(skip)]
-    pub headers: HashMap<String, String>,
+    pub headers: Vec<(String, String)>,
     #[wasm_bindgen(skip)]
-    pub body: Option<String>,
+    pub body: Option<Vec<u8>>,
 }
 
 #[wasm_bindgen]
@@ -171,9 +217,18 @@ impl WasmResponse {
         self.status_code.to_string()
     }
 
+    /// The body decoded as UTF-8, or `None` when absent or not valid UTF-8.
     #[wasm_bindgen(getter)]
     pub fn body(&self) -> Option<String> {
-        self.body.clone()
+        self.body
+            .as_ref()
+            .and_then(|bytes| String::from_utf8(bytes.clone()).ok())
+    }
+
+    /// The raw body bytes as a `Uint8Array`.
+    #[wasm_bindgen(getter)]
+    pub fn body_bytes(&self) -> Option<Uint8Array> {
+        self.body.as_ref().map(|bytes| Uint8Array::from(bytes.as_slice()))
     }
 
     #[wasm_bindgen(getter)]
@@ -185,25 +240,110 @@ impl WasmResponse {
 pub async fn axum_response_to_wasm_response(mut response: Response) -> Result<WasmResponse, Box<dyn std::error::Error>> {
     let status_code = response.status().to_string();
 
-    let mut headers = HashMap::new();
+    let mut headers = Vec::new();
     for (name, value) in response.headers() {
         if let Ok(value_str) = value.to_str() {
-            headers.insert(name.as_str().to_owned(), value_str.to_owned());
+            headers.push((name.as_str().to_owned(), value_str.to_owned()));
         }
     }
 
-    let bytes = match http_body::Body::data(response.body_mut()).await {
-        None => vec![],
-        Some(body_bytes) => match body_bytes {
-            Ok(bytes) => bytes.to_vec(),
-            Err(_) => vec![]
-        },
-    };
-    let body_str = String::from_utf8(bytes)?;
+    let mut collected = bytes::BytesMut::new();
+    while let Some(frame) = http_body::Body::data(response.body_mut()).await {
+        match frame {
+            Ok(bytes) => collected.extend_from_slice(&bytes),
+            Err(_) => break,
+        }
+    }
+    let bytes = collected.to_vec();
 
     Ok(WasmResponse {
         status_code,
         headers,
-        body: Some(body_str),
+        body: Some(bytes),
     })
-}
\ No newline at end of file
+}
+
+/// Maps an Axum response body onto a [`web_sys::ReadableStream`], enqueuing each
+/// frame as it resolves so large or long-lived responses don't have to be
+/// buffered entirely in memory before JS sees them.
+pub fn axum_response_to_readable_stream(response: Response) -> web_sys::ReadableStream {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let source = js_sys::Object::new();
+    let body = Rc::new(RefCell::new(Some(response.into_body())));
+
+    let start = Closure::once(move |controller: web_sys::ReadableStreamDefaultController| {
+        wasm_bindgen_futures::spawn_local(async move {
+            let mut body = body.borrow_mut().take().unwrap();
+            loop {
+                match http_body::Body::data(&mut body).await {
+                    Some(Ok(bytes)) => {
+                        let chunk = Uint8Array::from(bytes.as_ref());
+                        let _ = controller.enqueue_with_chunk(&chunk);
+                    }
+                    Some(Err(_)) | None => {
+                        let _ = controller.close();
+                        break;
+                    }
+                }
+            }
+        });
+    });
+
+    js_sys::Reflect::set(
+        &source,
+        &JsValue::from_str("start"),
+        start.as_ref().unchecked_ref(),
+    )
+    .unwrap();
+    start.forget();
+
+    web_sys::ReadableStream::new_with_underlying_source(&source).unwrap()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_utf8_request_body_converts_to_axum_request() {
+        // A non-UTF-8 payload (here a stray 0xFF byte) must survive the
+        // conversion instead of failing a `String::from_utf8` check.
+        let wasm_request = WasmRequest {
+            method: "POST".to_owned(),
+            url: "/upload".to_owned(),
+            headers: vec![],
+            body: Some(vec![0x00, 0xFF, 0x10, 0x80]),
+        };
+
+        let request = wasm_request_to_axum_request(&wasm_request).unwrap();
+
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.uri(), "/upload");
+    }
+
+    #[test]
+    fn duplicate_request_headers_reach_axum_intact() {
+        // Repeated headers (the `Set-Cookie` case on the request side) must not
+        // be collapsed when building the Axum request.
+        let wasm_request = WasmRequest {
+            method: "GET".to_owned(),
+            url: "/".to_owned(),
+            headers: vec![
+                ("set-cookie".to_owned(), "a=1".to_owned()),
+                ("set-cookie".to_owned(), "b=2".to_owned()),
+            ],
+            body: None,
+        };
+
+        let request = wasm_request_to_axum_request(&wasm_request).unwrap();
+
+        let values: Vec<_> = request
+            .headers()
+            .get_all("set-cookie")
+            .into_iter()
+            .map(|v| v.to_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(values, vec!["a=1".to_owned(), "b=2".to_owned()]);
+    }
+}
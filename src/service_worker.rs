@@ -0,0 +1,85 @@
+//! Service worker integration
+//!
+//! Installs a `fetch` event listener inside a [`ServiceWorkerGlobalScope`] that
+//! routes every intercepted request directly into an Axum [`Router`], turning the
+//! crate into a drop-in offline/PWA backend instead of a set of hand-written
+//! JavaScript recipes.
+
+use std::cell::RefCell;
+
+use axum::Router;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::future_to_promise;
+use web_sys::{FetchEvent, Request, ServiceWorkerGlobalScope};
+use tower_service::Service;
+
+use crate::{
+    axum_response_to_wasm_response, wasm_request_to_axum_request, wasm_response_to_web_response,
+    web_request_to_wasm_request,
+};
+
+thread_local! {
+    // wasm is single-threaded, so a thread-local `RefCell` is enough to let the
+    // router outlive the fetch callback without resorting to `static mut`.
+    static ROUTER: RefCell<Option<Router>> = const { RefCell::new(None) };
+}
+
+/// Installs an Axum [`Router`] as the handler for `fetch` events in a service worker.
+pub struct ServiceWorkerAdapter;
+
+impl ServiceWorkerAdapter {
+    /// Stores `router` in the (single-threaded) global slot and installs the
+    /// `fetch` listener on the current [`ServiceWorkerGlobalScope`].
+    pub fn handle_fetch_events(router: Router) -> Result<(), JsValue> {
+        ROUTER.with(|slot| slot.borrow_mut().replace(router));
+
+        let global: ServiceWorkerGlobalScope = js_sys::global().unchecked_into();
+
+        let callback = Closure::<dyn FnMut(FetchEvent)>::new(move |event: FetchEvent| {
+            let promise = future_to_promise(respond(event.request()));
+            let _ = event.respond_with(&promise);
+        });
+
+        global.add_event_listener_with_callback("fetch", callback.as_ref().unchecked_ref())?;
+        callback.forget();
+
+        Ok(())
+    }
+}
+
+/// Extension trait that lets a [`Router`] register itself as the service worker
+/// fetch handler.
+pub trait ServiceWorkerExt {
+    /// Installs `self` as the service worker `fetch` handler. See
+    /// [`ServiceWorkerAdapter::handle_fetch_events`].
+    fn handle_fetch_events(self) -> Result<(), JsValue>;
+}
+
+impl ServiceWorkerExt for Router {
+    fn handle_fetch_events(self) -> Result<(), JsValue> {
+        ServiceWorkerAdapter::handle_fetch_events(self)
+    }
+}
+
+async fn respond(request: Request) -> Result<JsValue, JsValue> {
+    let wasm_request = web_request_to_wasm_request(request).await;
+
+    let axum_request = wasm_request_to_axum_request(&wasm_request)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let mut router = ROUTER
+        .with(|slot| slot.borrow().clone())
+        .ok_or_else(|| JsValue::from_str("service worker router not registered"))?;
+
+    let axum_response = router
+        .call(axum_request)
+        .await
+        .map_err(|_| JsValue::from_str("router call failed"))?;
+
+    let wasm_response = axum_response_to_wasm_response(axum_response)
+        .await
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    Ok(wasm_response_to_web_response(wasm_response).into())
+}
@@ -0,0 +1,139 @@
+//! Browser console + performance tracing for Axum apps compiled to WASM.
+//!
+//! Enable the `tracing` feature and call [`init_tracing`] once at app start.
+//! Afterwards `tracing::info!` calls from handlers show up in the devtools
+//! console and span timings appear on the browser performance timeline.
+
+use std::fmt;
+use std::io;
+
+use tracing::{Level, Subscriber};
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::fmt::time::UtcTime;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Wires a [`tracing_subscriber`] registry to the browser console and the
+/// User Timing API. Safe to call once; subsequent calls are ignored.
+pub fn init_tracing() {
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_timer(UtcTime::rfc_3339())
+        .with_writer(MakeConsoleWriter);
+
+    let _ = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(PerformanceLayer)
+        .try_init();
+}
+
+/// A [`MakeWriter`] that routes each record to the matching `console.*` method.
+struct MakeConsoleWriter;
+
+impl<'a> MakeWriter<'a> for MakeConsoleWriter {
+    type Writer = ConsoleWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ConsoleWriter { level: Level::INFO, buffer: Vec::new() }
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        ConsoleWriter { level: *meta.level(), buffer: Vec::new() }
+    }
+}
+
+/// Buffers a single record and flushes it to the console on drop.
+struct ConsoleWriter {
+    level: Level,
+    buffer: Vec<u8>,
+}
+
+impl io::Write for ConsoleWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for ConsoleWriter {
+    fn drop(&mut self) {
+        let message = String::from_utf8_lossy(&self.buffer);
+        let message = JsValue::from_str(message.trim_end());
+        match self.level {
+            Level::TRACE | Level::DEBUG => web_sys::console::debug_1(&message),
+            Level::INFO => web_sys::console::info_1(&message),
+            Level::WARN => web_sys::console::warn_1(&message),
+            Level::ERROR => web_sys::console::error_1(&message),
+        }
+    }
+}
+
+/// Emits `performance.measure` marks for every span, tying span enter/exit to
+/// the User Timing API so span durations show up on the performance timeline.
+struct PerformanceLayer;
+
+impl PerformanceLayer {
+    fn performance() -> Option<web_sys::Performance> {
+        // The primary runtime is the service worker, where there is no `window`,
+        // so fall back to the worker global scope's `performance`.
+        if let Some(window) = web_sys::window() {
+            return window.performance();
+        }
+        js_sys::global()
+            .dyn_into::<web_sys::WorkerGlobalScope>()
+            .ok()
+            .and_then(|scope| scope.performance())
+    }
+
+    fn start_mark(id: &Id) -> String {
+        format!("axum-span-{}-start", id.into_u64())
+    }
+}
+
+impl<S> Layer<S> for PerformanceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanName(attrs.metadata().name().to_owned()));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, _ctx: Context<'_, S>) {
+        if let Some(performance) = Self::performance() {
+            let _ = performance.mark(&Self::start_mark(id));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let performance = match Self::performance() {
+            Some(performance) => performance,
+            None => return,
+        };
+
+        let name = ctx
+            .span(id)
+            .and_then(|span| span.extensions().get::<SpanName>().map(|n| n.0.clone()))
+            .unwrap_or_else(|| "axum-span".to_owned());
+        let start = Self::start_mark(id);
+
+        let _ = performance.measure_with_start_mark(&name, &start);
+    }
+}
+
+/// The span name, stashed in the span's extensions so it survives until exit.
+struct SpanName(String);
+
+impl fmt::Debug for SpanName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,129 @@
+//! Typed [`web_sys::Request`]/[`web_sys::Response`] conversions.
+//!
+//! These keep the whole request lifecycle in Rust so callers overriding `fetch`
+//! or wiring a service worker don't have to hand-write the JavaScript glue the
+//! README used to describe.
+
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Headers, Request, Response, ResponseInit};
+
+use crate::{WasmRequest, WasmResponse};
+
+/// Converts a [`web_sys::Request`] into a [`WasmRequest`], awaiting its body.
+///
+/// Reads the method and url, iterates `request.headers()` (preserving repeated
+/// entries), and pulls the body in via `array_buffer()`.
+pub async fn web_request_to_wasm_request(request: Request) -> WasmRequest {
+    let method = request.method();
+    let url = request.url();
+
+    let mut headers = Vec::new();
+    if let Ok(Some(iter)) = js_sys::try_iter(&request.headers().entries()) {
+        for entry in iter.flatten() {
+            let pair: js_sys::Array = entry.unchecked_into();
+            let key = pair.get(0).as_string().unwrap_or_default();
+            let value = pair.get(1).as_string().unwrap_or_default();
+            headers.push((key, value));
+        }
+    }
+
+    let body = match request.array_buffer() {
+        Ok(promise) => match JsFuture::from(promise).await {
+            Ok(buffer) => {
+                let bytes = Uint8Array::new(&buffer).to_vec();
+                if bytes.is_empty() { None } else { Some(bytes) }
+            }
+            Err(_) => None,
+        },
+        Err(_) => None,
+    };
+
+    WasmRequest { method, url, headers, body }
+}
+
+/// Converts a [`WasmResponse`] into a [`web_sys::Response`].
+///
+/// Builds a [`ResponseInit`] with the status and a constructed [`Headers`],
+/// emitting repeated header entries so values like `Set-Cookie` survive.
+pub fn wasm_response_to_web_response(response: WasmResponse) -> Response {
+    let headers = Headers::new().unwrap();
+    for (key, value) in &response.headers {
+        let _ = headers.append(key, value);
+    }
+
+    let status = parse_status(&response.status_code);
+
+    let init = ResponseInit::new();
+    init.set_status(status);
+    init.set_headers(&headers);
+
+    // Statuses that forbid a body (204/205/304) make the `Response` constructor
+    // throw if handed a non-null buffer, so drop the body for those.
+    let body = if status_forbids_body(status) {
+        None
+    } else {
+        response.body.as_deref()
+    };
+
+    match body {
+        Some(bytes) => {
+            let array = Uint8Array::from(bytes);
+            Response::new_with_opt_buffer_source_and_init(Some(&array), &init)
+        }
+        None => Response::new_with_opt_str_and_init(None, &init),
+    }
+    .unwrap()
+}
+
+/// Parses the leading numeric token of a status string such as `"404 Not Found"`
+/// (the `Display` form of [`http::StatusCode`]), falling back to `200`.
+fn parse_status(status_code: &str) -> u16 {
+    status_code
+        .split_whitespace()
+        .next()
+        .and_then(|token| token.parse().ok())
+        .unwrap_or(200)
+}
+
+/// Whether a status code forbids a response body per the Fetch spec.
+fn status_forbids_body(status: u16) -> bool {
+    matches!(status, 204 | 205 | 304)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_status_from_display_string() {
+        // `http::StatusCode`'s Display is "{code} {reason}"; only the leading
+        // numeric token is the status, so a 404 must stay a 404.
+        assert_eq!(parse_status("200 OK"), 200);
+        assert_eq!(parse_status("404 Not Found"), 404);
+        assert_eq!(parse_status("301 Moved Permanently"), 301);
+        assert_eq!(parse_status("500 Internal Server Error"), 500);
+    }
+
+    #[test]
+    fn parses_bare_numeric_status() {
+        assert_eq!(parse_status("204"), 204);
+    }
+
+    #[test]
+    fn falls_back_to_200_for_unparseable_status() {
+        assert_eq!(parse_status(""), 200);
+        assert_eq!(parse_status("not a status"), 200);
+    }
+
+    #[test]
+    fn null_body_statuses_forbid_a_body() {
+        assert!(status_forbids_body(204));
+        assert!(status_forbids_body(205));
+        assert!(status_forbids_body(304));
+        assert!(!status_forbids_body(200));
+        assert!(!status_forbids_body(404));
+    }
+}